@@ -0,0 +1,91 @@
+extern crate sdl2;
+
+use sdl2::audio::{AudioCallback, AudioDevice, AudioSpecDesired, AudioStatus};
+use sdl2::AudioSubsystem;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+const TONE_HZ: f32 = 440.0;
+const AMPLITUDE: f32 = 0.15;
+
+// Smoothing factor for the one-pole low-pass filter applied to the output.
+// Chopping a square wave off mid-cycle produces an audible click/ring, so
+// instead each output sample moves a fraction of the way toward the square
+// wave's current value.
+const LOW_PASS_ALPHA: f32 = 0.01;
+
+/// Square-wave oscillator gated by a shared flag. Runs on SDL2's audio
+/// thread, decoupled from the emulator core.
+struct SquareWave {
+    phase_inc: f32,
+    phase: f32,
+    current: f32,
+    beeping: Arc<AtomicBool>,
+}
+
+impl AudioCallback for SquareWave {
+    type Channel = f32;
+
+    fn callback(&mut self, out: &mut [f32]) {
+        let target = if self.beeping.load(Ordering::Relaxed) {
+            AMPLITUDE
+        } else {
+            0.0
+        };
+
+        for sample in out.iter_mut() {
+            let wave = if self.phase < 0.5 { target } else { -target };
+
+            // Ramp toward the (possibly zero) target instead of snapping to
+            // it, so beeps fade in/out rather than clicking.
+            self.current += LOW_PASS_ALPHA * (wave - self.current);
+            *sample = self.current;
+
+            self.phase = (self.phase + self.phase_inc) % 1.0;
+        }
+    }
+}
+
+/// Square-wave beeper driven by the CHIP-8 sound timer.
+///
+/// The device is opened paused and only starts playing once there's
+/// actually a beep to produce, so it doesn't pop on startup with silence.
+pub struct Audio {
+    device: AudioDevice<SquareWave>,
+    beeping: Arc<AtomicBool>,
+}
+
+impl Audio {
+    /// Open the default playback device, ready to be driven by
+    /// `Chip8::is_beeping`.
+    pub fn new(subsystem: &AudioSubsystem) -> Result<Audio, String> {
+        let beeping = Arc::new(AtomicBool::new(false));
+        let flag = beeping.clone();
+
+        let spec = AudioSpecDesired {
+            freq: Some(44_100),
+            channels: Some(1),
+            samples: None,
+        };
+
+        let device = subsystem.open_playback(None, &spec, |spec| SquareWave {
+            phase_inc: TONE_HZ / spec.freq as f32,
+            phase: 0.0,
+            current: 0.0,
+            beeping: flag,
+        })?;
+
+        Ok(Audio { device, beeping })
+    }
+
+    /// Update whether the callback should currently be producing sound.
+    /// Call this once per frame with `Chip8::is_beeping()`. The device is
+    /// only resumed here, the first time there's something to play.
+    pub fn set_beeping(&mut self, beeping: bool) {
+        self.beeping.store(beeping, Ordering::Relaxed);
+
+        if beeping && self.device.status() != AudioStatus::Playing {
+            self.device.resume();
+        }
+    }
+}